@@ -1,33 +1,254 @@
+use std::sync::{Mutex, OnceLock};
+
 use crate::client::MpesaResult;
+use crate::errors::MpesaError;
 use crate::Mpesa;
 use base64::encode;
-use openssl::rsa::Padding;
+use openssl::hash::{hash, MessageDigest};
+use openssl::pkey::Public;
+use openssl::rsa::{Padding, Rsa};
 use openssl::x509::X509;
 
+/// Maximum number of distinct certificates/credentials kept warm at once.
+/// A deployment that only ever talks to one environment with one initiator
+/// password only needs one slot, but this leaves headroom for the common
+/// "sandbox + production" or multi-tenant shape without falling back to an
+/// unbounded cache.
+const CACHE_CAPACITY: usize = 4;
+
+/// A cache key built from everything that determines the encrypted
+/// credential, without retaining the plaintext initiator password: the
+/// password is folded into a SHA-256 fingerprint so the cache never holds
+/// the secret itself any longer than the single `public_encrypt` call needs
+/// it for.
+type CredentialCacheKey = (String, Vec<u8>, i32);
+
+/// Bounded least-recently-used caches for the parsed public key and the
+/// final encrypted credential. Each is capped at `CACHE_CAPACITY` entries:
+/// a lookup promotes its entry to the front, an insert evicts the
+/// least-recently-used entry once the cache is full, so neither a
+/// credential rotation nor juggling a handful of distinct environments
+/// leaks entries for the life of the process.
+#[derive(Default)]
+struct SecurityCredentialCache {
+    public_keys: Vec<(String, Rsa<Public>)>,
+    credentials: Vec<(CredentialCacheKey, String)>,
+}
+
+impl SecurityCredentialCache {
+    fn get_public_key(&mut self, pem: &str) -> Option<Rsa<Public>> {
+        let pos = self.public_keys.iter().position(|(cached, _)| cached == pem)?;
+        let entry = self.public_keys.remove(pos);
+        let rsa_key = entry.1.clone();
+        self.public_keys.insert(0, entry);
+        Some(rsa_key)
+    }
+
+    fn insert_public_key(&mut self, pem: String, rsa_key: Rsa<Public>) {
+        self.public_keys.retain(|(cached, _)| cached != &pem);
+        self.public_keys.insert(0, (pem, rsa_key));
+        self.public_keys.truncate(CACHE_CAPACITY);
+    }
+
+    fn get_credential(&mut self, key: &CredentialCacheKey) -> Option<String> {
+        let pos = self.credentials.iter().position(|(cached, _)| cached == key)?;
+        let entry = self.credentials.remove(pos);
+        let credential = entry.1.clone();
+        self.credentials.insert(0, entry);
+        Some(credential)
+    }
+
+    fn insert_credential(&mut self, key: CredentialCacheKey, credential: String) {
+        self.credentials.retain(|(cached, _)| cached != &key);
+        self.credentials.insert(0, (key, credential));
+        self.credentials.truncate(CACHE_CAPACITY);
+    }
+}
+
+fn security_credential_cache() -> &'static Mutex<SecurityCredentialCache> {
+    static CACHE: OnceLock<Mutex<SecurityCredentialCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(SecurityCredentialCache::default()))
+}
+
 /// Trait responsible for implementation of security configs for Mpesa
 pub trait MpesaSecurity {
-    /// Generates security credentials
+    /// Generates security credentials using `Padding::PKCS1`.
     /// M-Pesa Core authenticates a transaction by decrypting the security credentials.
     /// Security credentials are generated by encrypting the base64 encoded initiator password with M-Pesa’s public key, a X509 certificate.
     /// Returns base64 encoded string.
     ///
     /// # Error
     /// Returns `EncryptionError` variant of `MpesaError`
-    fn gen_security_credentials(&self) -> MpesaResult<String>;
+    fn gen_security_credentials(&self) -> MpesaResult<String> {
+        self.gen_security_credentials_with_padding(Padding::PKCS1)
+    }
+
+    /// Generates security credentials the same way as `gen_security_credentials`,
+    /// but lets the caller pick the RSA padding scheme, for environments
+    /// that expect `Padding::PKCS1_OAEP` rather than the default PKCS1.
+    ///
+    /// # Error
+    /// Returns `MpesaError::Message` if the environment's certificate is
+    /// absent or malformed, and `EncryptionError` variant of `MpesaError` if
+    /// encryption itself fails.
+    fn gen_security_credentials_with_padding(&self, padding: Padding) -> MpesaResult<String>;
+}
+
+/// Encrypts `password` against `pem` using `padding`, going through the
+/// bounded public-key/credential caches. Pulled out of the trait impl so it
+/// can be exercised directly in tests without needing a real `Mpesa` client.
+fn gen_credential(pem: &str, password: &[u8], padding: Padding) -> MpesaResult<String> {
+    if pem.trim().is_empty() {
+        return Err(MpesaError::Message(
+            "no M-Pesa public certificate is configured for the current environment",
+        ));
+    }
+
+    let password_fingerprint = hash(MessageDigest::sha256(), password)?.to_vec();
+    let cache_key = (pem.to_string(), password_fingerprint, padding.as_raw());
+
+    let mut cache = security_credential_cache().lock().unwrap();
+
+    if let Some(cached_credential) = cache.get_credential(&cache_key) {
+        return Ok(cached_credential);
+    }
+
+    let rsa_key = match cache.get_public_key(pem) {
+        Some(rsa_key) => rsa_key,
+        None => {
+            let cert = X509::from_pem(pem.as_bytes()).map_err(|_| {
+                MpesaError::Message(
+                    "the M-Pesa public certificate for the current environment is missing or malformed",
+                )
+            })?;
+            let pub_key = cert.public_key()?;
+            let rsa_key = pub_key.rsa()?;
+            cache.insert_public_key(pem.to_string(), rsa_key.clone());
+            rsa_key
+        }
+    };
+
+    let buf_len = rsa_key.size() as usize;
+    let mut buffer = vec![0; buf_len];
+    rsa_key.public_encrypt(password, &mut buffer, padding)?;
+    let encoded = encode(&buffer);
+    // The buffer held the encrypted credential, not the plaintext password,
+    // but it's still sensitive material we're done with.
+    buffer.iter_mut().for_each(|byte| *byte = 0);
+
+    cache.insert_credential(cache_key, encoded.clone());
+
+    Ok(encoded)
 }
 
 impl MpesaSecurity for Mpesa {
-    fn gen_security_credentials(&self) -> MpesaResult<String> {
-        let pem = self.environment().get_certificate().as_bytes();
-        let cert = X509::from_pem(pem)?;
-        // getting the public and rsa keys
-        let pub_key = cert.public_key()?;
-        let rsa_key = pub_key.rsa()?;
-        // configuring the buffer
-        let buf_len = pub_key.size();
-        let mut buffer = vec![0; buf_len];
-
-        rsa_key.public_encrypt(self.initiator_password(), &mut buffer, Padding::PKCS1)?;
-        Ok(encode(buffer))
+    fn gen_security_credentials_with_padding(&self, padding: Padding) -> MpesaResult<String> {
+        let pem = self.environment().get_certificate().to_string();
+        gen_credential(&pem, self.initiator_password(), padding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Self-signed test-only certificate; never used against a real M-Pesa
+    // environment.
+    const TEST_CERT: &str = "-----BEGIN CERTIFICATE-----\n\
+MIIDCzCCAfOgAwIBAgIUJpkz/7y7lGj7eUejCs356da5/T8wDQYJKoZIhvcNAQEL\n\
+BQAwFTETMBEGA1UEAwwKbXBlc2EtdGVzdDAeFw0yNjA3MjgwNDAzNDFaFw0zNjA3\n\
+MjUwNDAzNDFaMBUxEzARBgNVBAMMCm1wZXNhLXRlc3QwggEiMA0GCSqGSIb3DQEB\n\
+AQUAA4IBDwAwggEKAoIBAQC5O4e88aH5hWj5TkfHQrM2Dsn2T1eqQj+dsp9Wvayn\n\
+VYWL7Llfu2+nVkYxjesQtP2fJJWOg0dMmtzCcW5krPWErXqRMFNb0sMqRYIApzSW\n\
+RVh/OkMrgjjFuC/IvlXVBlpqNMvnTJSh+IF4HFxSjMEutp6AAT2WpYlSoHPGa6iN\n\
+GkXa1siVB74Eh5Oz8owcgB1eQzmcbqQbGIUhZc87KTWSDs9F4fxCAKMS+byL/x5b\n\
+gUQ6EePBH1UoqanWClgsWhTLxENNDv79hgIBMIncc0cdM6+9BVP0ZtYPZsat+kyi\n\
+viSQsQYdwsm08gGzWKzLbHUZg/0y9ECtk+Wwarg3/tfvAgMBAAGjUzBRMB0GA1Ud\n\
+DgQWBBRbC+J5N+Wy4JjZ6yY4HpFiiHW5dzAfBgNVHSMEGDAWgBRbC+J5N+Wy4JjZ\n\
+6yY4HpFiiHW5dzAPBgNVHRMBAf8EBTADAQH/MA0GCSqGSIb3DQEBCwUAA4IBAQC2\n\
+lsrMD1Gqgoz025QZ9jvE2OmacMakIt21jInEFiJ+6QIlNfFXvegtqLs8C7JG3V2G\n\
+AULbwNCCfauinXngZ6NTO9tfISqhmMIFrkxgKZ8KHyQrDxLgNDX9kEJNJDKrzGKh\n\
+FuOTYGLOstDdnSoWOZUJVurij3y7Zsap+6VYslNTC44PCEgB2atpanGF0QdvZqk9\n\
+7Jv1/PFuqbcHfTVzXqfN/d7EKGrPWPup3Pe1Fm2LZTPaEv4JCxoZ/sRjG3InTBgr\n\
+Rdkz5kjtkv2nMMb9RbulgtXxwn0PhLOcV5v/UV8JEOwjyNzRwU64sUYdmdugigAo\n\
+IfvTHEN4bcJFAwrDjbaC\n\
+-----END CERTIFICATE-----\n";
+
+    // `security_credential_cache` is a process-wide static, so tests that
+    // touch it must run one at a time.
+    static TEST_GUARD: Mutex<()> = Mutex::new(());
+
+    fn locked_test<T>(test: T)
+    where
+        T: FnOnce(),
+    {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut cache = security_credential_cache().lock().unwrap();
+        cache.public_keys.clear();
+        cache.credentials.clear();
+        drop(cache);
+
+        test();
+    }
+
+    #[test]
+    fn empty_certificate_is_rejected() {
+        locked_test(|| {
+            let err = gen_credential("", b"initiator-password", Padding::PKCS1).unwrap_err();
+            assert!(matches!(
+                err,
+                MpesaError::Message(msg) if msg.contains("no M-Pesa public certificate")
+            ));
+        });
+    }
+
+    #[test]
+    fn malformed_certificate_is_rejected() {
+        locked_test(|| {
+            let err =
+                gen_credential("not a real certificate", b"initiator-password", Padding::PKCS1)
+                    .unwrap_err();
+            assert!(matches!(
+                err,
+                MpesaError::Message(msg) if msg.contains("missing or malformed")
+            ));
+        });
+    }
+
+    #[test]
+    fn repeated_calls_with_identical_inputs_hit_the_cache() {
+        locked_test(|| {
+            let first = gen_credential(TEST_CERT, b"initiator-password", Padding::PKCS1).unwrap();
+            let second =
+                gen_credential(TEST_CERT, b"initiator-password", Padding::PKCS1).unwrap();
+
+            assert_eq!(first, second);
+            assert_eq!(
+                security_credential_cache().lock().unwrap().credentials.len(),
+                1
+            );
+        });
+    }
+
+    #[test]
+    fn changing_the_password_evicts_the_old_cached_credential_once_capacity_is_exceeded() {
+        locked_test(|| {
+            for i in 0..=CACHE_CAPACITY {
+                let password = format!("initiator-password-{i}");
+                gen_credential(TEST_CERT, password.as_bytes(), Padding::PKCS1).unwrap();
+            }
+
+            let cache = security_credential_cache().lock().unwrap();
+            assert_eq!(cache.credentials.len(), CACHE_CAPACITY);
+
+            let first_password_fingerprint =
+                hash(MessageDigest::sha256(), b"initiator-password-0")
+                    .unwrap()
+                    .to_vec();
+            assert!(!cache
+                .credentials
+                .iter()
+                .any(|((_, fingerprint, _), _)| fingerprint == &first_password_fingerprint));
+        });
     }
 }
@@ -0,0 +1,216 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::client::Mpesa;
+use crate::constants::{CommandId, IdentifierTypes};
+use crate::errors::{MpesaError, MpesaResult};
+use crate::MpesaSecurity;
+
+const BUSINESS_PAYBILL_URL: &str = "mpesa/b2b/v1/paymentrequest";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BusinessPayBillRequest<'mpesa> {
+    /// This is the credential/username used to authenticate the transaction
+    /// request.
+    pub initiator: &'mpesa str,
+    /// Base64 encoded string of the initiator password, which is encrypted
+    /// using M-Pesa public key and validates the transaction on M-Pesa Core
+    /// system.
+    pub security_credential: String,
+    /// Unique command for each transaction type, `BusinessPayBill` unless
+    /// set otherwise on `BusinessPayBillBuilder`.
+    pub command_id: CommandId,
+    /// Type of organization sending the transaction.
+    pub sender_identifier_type: IdentifierTypes,
+    /// Type of organization receiving the funds being transacted.
+    pub receiver_identifier_type: IdentifierTypes,
+    /// The amount being transacted.
+    pub amount: u32,
+    /// Organization's short code initiating the transaction.
+    pub party_a: &'mpesa str,
+    /// Short code of the organization receiving the funds, i.e. the paybill
+    /// number or paybill store.
+    pub party_b: &'mpesa str,
+    /// Account number, as defined by the receiving organization, to which
+    /// the payment should be applied.
+    pub account_reference: &'mpesa str,
+    /// Optional parameter, required for transactions on behalf of a
+    /// Consumer.
+    pub requester: Option<&'mpesa str>,
+    /// Any additional information to be associated with the transaction.
+    pub remarks: &'mpesa str,
+    /// The path that stores information about the time-out transaction.
+    #[serde(rename = "QueueTimeOutURL")]
+    pub queue_time_out_url: Url,
+    /// The path that stores information about the transaction.
+    #[serde(rename = "ResultURL")]
+    pub result_url: Url,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BusinessPayBillResponse {
+    /// The unique request ID for tracking a transaction.
+    #[serde(rename = "OriginatorConversationID")]
+    pub originator_conversation_id: String,
+    /// The unique request ID returned by M-Pesa for each request made.
+    #[serde(rename = "ConversationID")]
+    pub conversation_id: String,
+    /// Response description is an acknowledgment message from the API that
+    /// gives the status of the request submission.
+    pub response_description: String,
+}
+
+/// Default `command_id` applied by `BusinessPayBillBuilder` when
+/// `command_id` isn't set explicitly.
+const DEFAULT_COMMAND_ID: CommandId = CommandId::BusinessPayBill;
+
+/// Default `sender_identifier_type`/`receiver_identifier_type` applied by
+/// `BusinessPayBillBuilder` when those setters aren't called.
+const DEFAULT_IDENTIFIER_TYPE: IdentifierTypes = IdentifierTypes::ShortCode;
+
+#[derive(Builder, Debug, Clone)]
+#[builder(build_fn(error = "MpesaError"))]
+pub struct BusinessPayBill<'mpesa> {
+    #[builder(pattern = "immutable")]
+    client: &'mpesa Mpesa,
+    /// This is the credential/username used to authenticate the transaction
+    /// request.
+    #[builder(setter(into))]
+    initiator: &'mpesa str,
+    /// Unique command for each transaction type, defaults to
+    /// `BusinessPayBill`.
+    #[builder(default = "DEFAULT_COMMAND_ID")]
+    command_id: CommandId,
+    /// Type of organization sending the transaction.
+    #[builder(setter(into), default = "DEFAULT_IDENTIFIER_TYPE")]
+    sender_identifier_type: IdentifierTypes,
+    /// Type of organization receiving the funds being transacted.
+    #[builder(setter(into), default = "DEFAULT_IDENTIFIER_TYPE")]
+    receiver_identifier_type: IdentifierTypes,
+    /// The amount being transacted.
+    amount: u32,
+    /// Organization's short code initiating the transaction.
+    #[builder(setter(into))]
+    party_a: &'mpesa str,
+    /// Short code of the organization receiving the funds, i.e. the paybill
+    /// number or paybill store.
+    #[builder(setter(into))]
+    party_b: &'mpesa str,
+    /// Account number, as defined by the receiving organization, to which
+    /// the payment should be applied.
+    #[builder(setter(into))]
+    account_reference: &'mpesa str,
+    /// Optional parameter, required for transactions on behalf of a
+    /// Consumer.
+    #[builder(setter(into, strip_option), default)]
+    requester: Option<&'mpesa str>,
+    /// Any additional information to be associated with the transaction.
+    #[builder(setter(into))]
+    remarks: &'mpesa str,
+    /// The path that stores information about the time-out transaction.
+    #[builder(try_setter, setter(into))]
+    queue_time_out_url: Url,
+    /// The path that stores information about the transaction.
+    #[builder(try_setter, setter(into))]
+    result_url: Url,
+}
+
+impl<'mpesa> BusinessPayBill<'mpesa> {
+    /// Creates new `BusinessPayBillBuilder`
+    pub(crate) fn builder(client: &'mpesa Mpesa) -> BusinessPayBillBuilder<'mpesa> {
+        BusinessPayBillBuilder::default().client(client)
+    }
+
+    /// # Business PayBill
+    ///
+    /// Pays a business's paybill number or paybill store on behalf of a
+    /// business account, distinct from the generic B2B path in that it
+    /// targets a paybill rather than a till.
+    ///
+    /// A successful request returns a `BusinessPayBillResponse` type
+    ///
+    /// # Errors
+    /// Returns a `MpesaError` on failure
+    pub async fn send(self) -> MpesaResult<BusinessPayBillResponse> {
+        let security_credential = self.client.gen_security_credentials()?;
+
+        let request = BusinessPayBillRequest {
+            initiator: self.initiator,
+            security_credential,
+            command_id: self.command_id,
+            sender_identifier_type: self.sender_identifier_type,
+            receiver_identifier_type: self.receiver_identifier_type,
+            amount: self.amount,
+            party_a: self.party_a,
+            party_b: self.party_b,
+            account_reference: self.account_reference,
+            requester: self.requester,
+            remarks: self.remarks,
+            queue_time_out_url: self.queue_time_out_url,
+            result_url: self.result_url,
+        };
+
+        self.client
+            .send::<BusinessPayBillRequest, _>(crate::client::Request {
+                method: reqwest::Method::POST,
+                path: BUSINESS_PAYBILL_URL,
+                body: request,
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_request() -> BusinessPayBillRequest<'static> {
+        BusinessPayBillRequest {
+            initiator: "initiator",
+            security_credential: "encrypted-credential".to_string(),
+            command_id: DEFAULT_COMMAND_ID,
+            sender_identifier_type: DEFAULT_IDENTIFIER_TYPE,
+            receiver_identifier_type: DEFAULT_IDENTIFIER_TYPE,
+            amount: 1000,
+            party_a: "600000",
+            party_b: "600001",
+            account_reference: "account",
+            requester: None,
+            remarks: "paybill payment",
+            queue_time_out_url: "https://example.com/timeout".parse().unwrap(),
+            result_url: "https://example.com/result".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn default_command_id_and_identifier_types_are_business_paybill_and_short_code() {
+        let request = test_request();
+
+        assert_eq!(request.command_id, CommandId::BusinessPayBill);
+        assert_eq!(request.sender_identifier_type, IdentifierTypes::ShortCode);
+        assert_eq!(request.receiver_identifier_type, IdentifierTypes::ShortCode);
+    }
+
+    #[test]
+    fn deserializes_business_paybill_response() {
+        let json = r#"{
+            "OriginatorConversationID": "29112-34801843-1",
+            "ConversationID": "AG_20191219_00005797af5d7d75f652",
+            "ResponseDescription": "Accept the service request successfully."
+        }"#;
+
+        let response: BusinessPayBillResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.originator_conversation_id, "29112-34801843-1");
+        assert_eq!(
+            response.conversation_id,
+            "AG_20191219_00005797af5d7d75f652"
+        );
+        assert_eq!(
+            response.response_description,
+            "Accept the service request successfully."
+        );
+    }
+}
@@ -0,0 +1,13 @@
+mod business_paybill_request;
+
+pub use business_paybill_request::*;
+
+use crate::client::Mpesa;
+
+impl Mpesa {
+    /// Creates a `BusinessPayBillBuilder` for paying a business's paybill
+    /// number or paybill store.
+    pub fn business_paybill(&self) -> BusinessPayBillBuilder {
+        BusinessPayBill::builder(self)
+    }
+}
@@ -0,0 +1,197 @@
+use serde::de::Deserializer;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::MpesaResult;
+
+/// The payload Safaricom POSTs to the `callback_url` configured on
+/// `MpesaExpress` once the customer has responded to (or the gateway has
+/// given up on) the STK push prompt.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StkCallback {
+    #[serde(rename = "Body")]
+    pub body: StkCallbackBody,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StkCallbackBody {
+    #[serde(rename = "stkCallback")]
+    pub stk_callback: StkCallbackResult,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StkCallbackResult {
+    /// This is a global unique Identifier for the submitted payment request.
+    #[serde(rename = "MerchantRequestID")]
+    pub merchant_request_id: String,
+    /// This is a global unique identifier of the processed checkout
+    /// transaction request.
+    #[serde(rename = "CheckoutRequestID")]
+    pub checkout_request_id: String,
+    /// 0 means the transaction was processed successfully, any other value
+    /// is an error code.
+    pub result_code: i32,
+    /// Describes the result of the transaction as indicated by
+    /// `result_code`.
+    pub result_desc: String,
+    /// Flattened view of `CallbackMetadata.Item`, present only on a
+    /// successful transaction (`result_code == 0`).
+    #[serde(
+        rename = "CallbackMetadata",
+        default,
+        deserialize_with = "deserialize_callback_metadata"
+    )]
+    pub callback_metadata: Option<CallbackMetadata>,
+}
+
+/// Flattened view of the `CallbackMetadata.Item` array, which Safaricom
+/// sends as an untyped list of `{Name, Value}` pairs. Failed transactions
+/// omit `CallbackMetadata` entirely, so every field here is optional.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CallbackMetadata {
+    pub amount: Option<f64>,
+    pub mpesa_receipt_number: Option<String>,
+    pub transaction_date: Option<i64>,
+    pub phone_number: Option<i64>,
+    pub balance: Option<f64>,
+}
+
+/// Deserializes the `{"Item": [{"Name": "...", "Value": ...}, ...]}` shape
+/// into a `CallbackMetadata`, tolerating missing items and an absent
+/// `CallbackMetadata` object altogether.
+fn deserialize_callback_metadata<'de, D>(
+    deserializer: D,
+) -> Result<Option<CallbackMetadata>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Wrapper {
+        #[serde(rename = "Item")]
+        item: Vec<Item>,
+    }
+
+    #[derive(Deserialize)]
+    struct Item {
+        #[serde(rename = "Name")]
+        name: String,
+        #[serde(rename = "Value", default)]
+        value: Option<Value>,
+    }
+
+    let wrapper = match Option::<Wrapper>::deserialize(deserializer)? {
+        Some(wrapper) => wrapper,
+        None => return Ok(None),
+    };
+
+    let mut metadata = CallbackMetadata::default();
+    for item in wrapper.item {
+        match item.name.as_str() {
+            "Amount" => metadata.amount = item.value.and_then(|v| v.as_f64()),
+            "MpesaReceiptNumber" => {
+                metadata.mpesa_receipt_number =
+                    item.value.and_then(|v| v.as_str().map(String::from))
+            }
+            "TransactionDate" => metadata.transaction_date = item.value.and_then(|v| v.as_i64()),
+            "PhoneNumber" => metadata.phone_number = item.value.and_then(|v| v.as_i64()),
+            "Balance" => metadata.balance = item.value.and_then(|v| v.as_f64()),
+            _ => {}
+        }
+    }
+
+    Ok(Some(metadata))
+}
+
+impl StkCallback {
+    /// Parses a raw STK push callback body, as received on the endpoint
+    /// configured as `MpesaExpress`'s `callback_url`.
+    ///
+    /// # Errors
+    /// Returns a `MpesaError` if the payload doesn't match the expected
+    /// shape.
+    pub fn from_json(bytes: &[u8]) -> MpesaResult<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_successful_callback_with_metadata() {
+        let json = r#"{
+            "Body": {
+                "stkCallback": {
+                    "MerchantRequestID": "merchant-1",
+                    "CheckoutRequestID": "checkout-1",
+                    "ResultCode": 0,
+                    "ResultDesc": "The service request is processed successfully.",
+                    "CallbackMetadata": {
+                        "Item": [
+                            {"Name": "Amount", "Value": 1.00},
+                            {"Name": "MpesaReceiptNumber", "Value": "NLJ7RT61SV"},
+                            {"Name": "TransactionDate", "Value": 20191219102115},
+                            {"Name": "PhoneNumber", "Value": 254708374149}
+                        ]
+                    }
+                }
+            }
+        }"#;
+
+        let callback = StkCallback::from_json(json.as_bytes()).unwrap();
+        let result = callback.body.stk_callback;
+        assert_eq!(result.result_code, 0);
+        let metadata = result.callback_metadata.unwrap();
+        assert_eq!(metadata.amount, Some(1.00));
+        assert_eq!(metadata.mpesa_receipt_number, Some("NLJ7RT61SV".to_string()));
+        assert_eq!(metadata.transaction_date, Some(20191219102115));
+        assert_eq!(metadata.phone_number, Some(254708374149));
+        assert_eq!(metadata.balance, None);
+    }
+
+    #[test]
+    fn parses_failed_callback_without_metadata() {
+        let json = r#"{
+            "Body": {
+                "stkCallback": {
+                    "MerchantRequestID": "merchant-2",
+                    "CheckoutRequestID": "checkout-2",
+                    "ResultCode": 1032,
+                    "ResultDesc": "Request cancelled by user"
+                }
+            }
+        }"#;
+
+        let callback = StkCallback::from_json(json.as_bytes()).unwrap();
+        let result = callback.body.stk_callback;
+        assert_eq!(result.result_code, 1032);
+        assert!(result.callback_metadata.is_none());
+    }
+
+    #[test]
+    fn tolerates_unknown_and_missing_items() {
+        let json = r#"{
+            "Body": {
+                "stkCallback": {
+                    "MerchantRequestID": "merchant-3",
+                    "CheckoutRequestID": "checkout-3",
+                    "ResultCode": 0,
+                    "ResultDesc": "Success",
+                    "CallbackMetadata": {
+                        "Item": [
+                            {"Name": "Amount", "Value": 5.00},
+                            {"Name": "SomeFutureField", "Value": "unused"}
+                        ]
+                    }
+                }
+            }
+        }"#;
+
+        let callback = StkCallback::from_json(json.as_bytes()).unwrap();
+        let metadata = callback.body.stk_callback.callback_metadata.unwrap();
+        assert_eq!(metadata.amount, Some(5.00));
+        assert_eq!(metadata.mpesa_receipt_number, None);
+    }
+}
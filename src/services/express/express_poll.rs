@@ -0,0 +1,143 @@
+use std::time::{Duration, Instant};
+
+use super::express_query::{MpesaExpressQuery, MpesaExpressQueryResponse};
+use super::express_request::MpesaExpress;
+use crate::errors::{MpesaError, MpesaResult};
+
+const DEFAULT_INITIAL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_ATTEMPTS: u32 = 10;
+
+/// Terminal outcome of an STK push transaction, as resolved by polling
+/// `MpesaExpressQuery` via `MpesaExpress::send_and_poll`.
+#[derive(Debug, Clone)]
+pub enum StkOutcome {
+    /// The customer entered their PIN and the transaction went through.
+    Completed(MpesaExpressQueryResponse),
+    /// The customer cancelled the STK push prompt (`ResultCode` 1032).
+    Cancelled,
+    /// Safaricom's gateway timed out waiting for the customer to respond
+    /// (`ResultCode` 1037).
+    TimedOut,
+    /// The customer didn't have enough funds to complete the transaction
+    /// (`ResultCode` 1).
+    InsufficientFunds,
+    /// Any other terminal failure, carrying the final query response.
+    Failed(MpesaExpressQueryResponse),
+}
+
+/// Maps a `MpesaExpressQuery` response's `ResultCode` to its `StkOutcome`.
+fn classify_result(response: MpesaExpressQueryResponse) -> StkOutcome {
+    match response.result_code.as_str() {
+        "0" => StkOutcome::Completed(response),
+        "1032" => StkOutcome::Cancelled,
+        "1037" => StkOutcome::TimedOut,
+        "1" => StkOutcome::InsufficientFunds,
+        _ => StkOutcome::Failed(response),
+    }
+}
+
+impl<'mpesa> MpesaExpress<'mpesa> {
+    /// Initiates the STK push and polls `MpesaExpressQuery` until the
+    /// transaction reaches a terminal state.
+    ///
+    /// Polling starts at `initial_interval` (default 5 seconds) and doubles
+    /// after each attempt, capped at 30 seconds, stopping once
+    /// `max_attempts` (default 10) is reached or `deadline` elapses,
+    /// whichever comes first. Use `MpesaExpressBuilder`'s `max_attempts`,
+    /// `initial_interval` and `deadline` setters to override the defaults.
+    ///
+    /// # Errors
+    /// Returns a `MpesaError` if the initial push fails, or if polling is
+    /// exhausted without the transaction reaching a terminal state.
+    pub async fn send_and_poll(self) -> MpesaResult<StkOutcome> {
+        let client = self.client;
+        let business_short_code = self.business_short_code;
+        let pass_key = self.pass_key;
+        let max_attempts = self.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        let deadline = self.deadline;
+        let mut interval = self.initial_interval.unwrap_or(DEFAULT_INITIAL_INTERVAL);
+
+        let push_response = self.send().await?;
+        let checkout_request_id = push_response.checkout_request_id;
+
+        let started_at = Instant::now();
+        let mut last_err = None;
+
+        for _ in 0..max_attempts {
+            if let Some(deadline) = deadline {
+                if started_at.elapsed() >= deadline {
+                    break;
+                }
+            }
+
+            tokio::time::sleep(interval).await;
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+
+            let mut builder = MpesaExpressQuery::builder(client);
+            builder
+                .business_short_code(business_short_code)
+                .checkout_request_id(checkout_request_id.as_str());
+            if let Some(pass_key) = pass_key {
+                builder.pass_key(pass_key);
+            }
+
+            match builder.build()?.send().await {
+                Ok(response) => return Ok(classify_result(response)),
+                // The transaction is most likely still awaiting the
+                // customer's PIN; keep polling until it resolves.
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err.unwrap_or(MpesaError::Message(
+            "STK push polling exhausted its attempts/deadline without a terminal result",
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_with_result_code(result_code: &str) -> MpesaExpressQueryResponse {
+        MpesaExpressQueryResponse {
+            response_code: "0".to_string(),
+            response_description: "Success".to_string(),
+            merchant_request_id: "merchant-1".to_string(),
+            checkout_request_id: "checkout-1".to_string(),
+            result_code: result_code.to_string(),
+            result_desc: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn classifies_completed_transaction() {
+        let outcome = classify_result(response_with_result_code("0"));
+        assert!(matches!(outcome, StkOutcome::Completed(_)));
+    }
+
+    #[test]
+    fn classifies_user_cancellation() {
+        let outcome = classify_result(response_with_result_code("1032"));
+        assert!(matches!(outcome, StkOutcome::Cancelled));
+    }
+
+    #[test]
+    fn classifies_ds_timeout() {
+        let outcome = classify_result(response_with_result_code("1037"));
+        assert!(matches!(outcome, StkOutcome::TimedOut));
+    }
+
+    #[test]
+    fn classifies_insufficient_funds() {
+        let outcome = classify_result(response_with_result_code("1"));
+        assert!(matches!(outcome, StkOutcome::InsufficientFunds));
+    }
+
+    #[test]
+    fn classifies_unknown_result_code_as_failed() {
+        let outcome = classify_result(response_with_result_code("2001"));
+        assert!(matches!(outcome, StkOutcome::Failed(_)));
+    }
+}
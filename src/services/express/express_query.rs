@@ -0,0 +1,162 @@
+use chrono::prelude::Local;
+use chrono::DateTime;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::express_request::MpesaExpress;
+use super::{serialize_utc_to_string, DEFAULT_PASSKEY};
+use crate::client::Mpesa;
+use crate::errors::{MpesaError, MpesaResult};
+
+const EXPRESS_QUERY_REQUEST_URL: &str = "mpesa/stkpushquery/v1/query";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MpesaExpressQueryRequest<'mpesa> {
+    /// This is the organization's shortcode (Paybill or Buygoods - A 5 to
+    /// 6-digit account number) used to identify an organization and receive
+    /// the transaction.
+    pub business_short_code: &'mpesa str,
+    /// This is the password used for encrypting the request sent. It is
+    /// freshly generated against this query's own `timestamp`, not reused
+    /// from the password sent with the original STK push.
+    pub password: String,
+    /// This is the Timestamp of the transaction, normally in the format of
+    /// (YYYYMMDDHHMMSS)
+    #[serde(serialize_with = "serialize_utc_to_string")]
+    pub timestamp: DateTime<Local>,
+    /// This is a global unique identifier of the processed checkout
+    /// transaction request, as returned by `MpesaExpress::send`.
+    #[serde(rename = "CheckoutRequestID")]
+    pub checkout_request_id: &'mpesa str,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MpesaExpressQueryResponse {
+    /// This is a Numeric status code that indicates the status of the
+    /// transaction submission. 0 means successful submission and any other
+    /// code means an error occurred.
+    pub response_code: String,
+    /// Response description is an acknowledgment message from the API that
+    /// gives the status of the request submission.
+    pub response_description: String,
+    /// This is a global unique Identifier for the submitted payment request.
+    #[serde(rename = "MerchantRequestID")]
+    pub merchant_request_id: String,
+    /// This is a global unique identifier of the processed checkout
+    /// transaction request.
+    #[serde(rename = "CheckoutRequestID")]
+    pub checkout_request_id: String,
+    /// Indicates the final status of the transaction. 0 means the customer
+    /// completed the payment; any other code is a terminal failure (see
+    /// Safaricom's result code reference).
+    pub result_code: String,
+    /// Describes the result of the transaction as indicated by
+    /// `result_code`.
+    pub result_desc: String,
+}
+
+#[derive(Builder, Debug, Clone)]
+#[builder(build_fn(error = "MpesaError"))]
+pub struct MpesaExpressQuery<'mpesa> {
+    #[builder(pattern = "immutable")]
+    pub(crate) client: &'mpesa Mpesa,
+    /// This is the organization's shortcode (Paybill or Buygoods - A 5 to
+    /// 6-digit account number) used to identify an organization and receive
+    /// the transaction.
+    #[builder(setter(into))]
+    pub(crate) business_short_code: &'mpesa str,
+    /// This is a global unique identifier of the processed checkout
+    /// transaction request, as returned by `MpesaExpress::send`.
+    #[builder(setter(into))]
+    pub(crate) checkout_request_id: &'mpesa str,
+    /// This is the password used for encrypting the request sent:
+    /// The password for encrypting the request is obtained by base64 encoding
+    /// BusinessShortCode, Passkey and Timestamp.
+    #[builder(setter(into, strip_option), default = "Some(DEFAULT_PASSKEY)")]
+    pub(crate) pass_key: Option<&'mpesa str>,
+}
+
+impl<'mpesa> MpesaExpressQueryRequest<'mpesa> {
+    /// Builds the request, encoding `password` against `timestamp`. Pulled
+    /// out of the `From` impl so the password/timestamp pairing can be
+    /// exercised directly in tests without needing a real `MpesaExpressQuery`.
+    fn new(
+        business_short_code: &'mpesa str,
+        checkout_request_id: &'mpesa str,
+        pass_key: Option<&'mpesa str>,
+        timestamp: DateTime<Local>,
+    ) -> MpesaExpressQueryRequest<'mpesa> {
+        let encoded_password =
+            MpesaExpress::encode_password(business_short_code, pass_key, timestamp);
+
+        MpesaExpressQueryRequest {
+            business_short_code,
+            password: encoded_password,
+            timestamp,
+            checkout_request_id,
+        }
+    }
+}
+
+impl<'mpesa> From<MpesaExpressQuery<'mpesa>> for MpesaExpressQueryRequest<'mpesa> {
+    fn from(query: MpesaExpressQuery<'mpesa>) -> MpesaExpressQueryRequest<'mpesa> {
+        let timestamp = chrono::Local::now();
+
+        MpesaExpressQueryRequest::new(
+            query.business_short_code,
+            query.checkout_request_id,
+            query.pass_key,
+            timestamp,
+        )
+    }
+}
+
+impl<'mpesa> MpesaExpressQuery<'mpesa> {
+    /// Creates new `MpesaExpressQueryBuilder`
+    pub(crate) fn builder(client: &'mpesa Mpesa) -> MpesaExpressQueryBuilder<'mpesa> {
+        MpesaExpressQueryBuilder::default().client(client)
+    }
+
+    /// # Lipa na M-Pesa Online Payment Query / Mpesa Express Query
+    ///
+    /// Checks the status of an STK push transaction previously initiated via
+    /// `MpesaExpress::send`.
+    ///
+    /// A successful request returns a `MpesaExpressQueryResponse` type
+    ///
+    /// # Errors
+    /// Returns a `MpesaError` on failure
+    pub async fn send(self) -> MpesaResult<MpesaExpressQueryResponse> {
+        self.client
+            .send::<MpesaExpressQueryRequest, _>(crate::client::Request {
+                method: reqwest::Method::POST,
+                path: EXPRESS_QUERY_REQUEST_URL,
+                body: self.into(),
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_password_is_encoded_against_its_own_timestamp() {
+        let timestamp = chrono::Local::now();
+        let request = MpesaExpressQueryRequest::new(
+            "174379",
+            "ws_CO_1",
+            Some("test-pass-key"),
+            timestamp,
+        );
+
+        let expected_password =
+            MpesaExpress::encode_password("174379", Some("test-pass-key"), timestamp);
+
+        assert_eq!(request.password, expected_password);
+        assert_eq!(request.timestamp, timestamp);
+    }
+}
@@ -1,5 +1,7 @@
 #![doc = include_str!("../../../docs/client/express.md")]
 
+use std::time::Duration;
+
 use chrono::prelude::Local;
 use chrono::DateTime;
 use derive_builder::Builder;
@@ -90,12 +92,12 @@ pub struct MpesaExpressResponse {
 #[builder(build_fn(error = "MpesaError", validate = "Self::validate"))]
 pub struct MpesaExpress<'mpesa> {
     #[builder(pattern = "immutable")]
-    client: &'mpesa Mpesa,
+    pub(crate) client: &'mpesa Mpesa,
     /// This is the organization's shortcode (Paybill or Buygoods - A 5 to
     /// 6-digit account number) used to identify an organization and receive
     /// the transaction.
     #[builder(setter(into))]
-    business_short_code: &'mpesa str,
+    pub(crate) business_short_code: &'mpesa str,
     /// This is the transaction type that is used to identify the transaction
     /// when sending the request to M-PESA
     ///
@@ -130,7 +132,20 @@ pub struct MpesaExpress<'mpesa> {
     /// BusinessShortCode, Passkey and Timestamp.
     /// The timestamp format is YYYYMMDDHHmmss
     #[builder(setter(into, strip_option), default = "Some(DEFAULT_PASSKEY)")]
-    pass_key: Option<&'mpesa str>,
+    pub(crate) pass_key: Option<&'mpesa str>,
+    /// Maximum number of polling attempts `send_and_poll` will make before
+    /// giving up. Defaults to 10.
+    #[builder(setter(strip_option), default)]
+    pub(crate) max_attempts: Option<u32>,
+    /// Delay before the first polling attempt made by `send_and_poll`,
+    /// doubling after each subsequent attempt up to a 30 second cap.
+    /// Defaults to 5 seconds.
+    #[builder(setter(strip_option), default)]
+    pub(crate) initial_interval: Option<Duration>,
+    /// Overall deadline `send_and_poll` will poll for, on top of
+    /// `max_attempts`. Defaults to no deadline.
+    #[builder(setter(strip_option), default)]
+    pub(crate) deadline: Option<Duration>,
 }
 
 impl<'mpesa> From<MpesaExpress<'mpesa>> for MpesaExpressRequest<'mpesa> {
@@ -138,7 +153,7 @@ impl<'mpesa> From<MpesaExpress<'mpesa>> for MpesaExpressRequest<'mpesa> {
         let timestamp = chrono::Local::now();
 
         let encoded_password =
-            MpesaExpress::encode_password(express.business_short_code, express.pass_key);
+            MpesaExpress::encode_password(express.business_short_code, express.pass_key, timestamp);
 
         MpesaExpressRequest {
             business_short_code: express.business_short_code,
@@ -188,8 +203,18 @@ impl<'mpesa> MpesaExpress<'mpesa> {
     /// The password for encrypting the request is obtained by base64 encoding
     /// BusinessShortCode, Passkey and Timestamp.
     /// The timestamp format is YYYYMMDDHHmmss
-    pub fn encode_password(business_short_code: &str, pass_key: Option<&'mpesa str>) -> String {
-        let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S").to_string();
+    ///
+    /// `timestamp` must be the exact same value serialized into the
+    /// request's `Timestamp` field: M-Pesa recomputes the expected password
+    /// from the `Timestamp` it receives, so encoding against a second,
+    /// independently-captured timestamp can produce a password that no
+    /// longer matches and fail authentication intermittently.
+    pub fn encode_password(
+        business_short_code: &str,
+        pass_key: Option<&'mpesa str>,
+        timestamp: DateTime<Local>,
+    ) -> String {
+        let timestamp = timestamp.format("%Y%m%d%H%M%S").to_string();
         base64::encode_block(
             format!(
                 "{}{}{}",
@@ -219,6 +244,9 @@ impl<'mpesa> MpesaExpress<'mpesa> {
             account_ref: request.account_reference,
             transaction_desc: request.transaction_desc,
             pass_key,
+            max_attempts: None,
+            initial_interval: None,
+            deadline: None,
         }
     }
 
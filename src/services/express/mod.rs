@@ -0,0 +1,39 @@
+mod callbacks;
+mod express_poll;
+mod express_query;
+mod express_request;
+
+pub use callbacks::*;
+pub use express_poll::*;
+pub use express_query::*;
+pub use express_request::*;
+
+use chrono::{DateTime, Local};
+use serde::Serializer;
+
+use crate::client::Mpesa;
+
+/// The publicly documented Safaricom sandbox passkey for shortcode 174379,
+/// used as the default `pass_key` when callers don't supply their own.
+pub(crate) const DEFAULT_PASSKEY: &str =
+    "bfb279f9aa9bdbcf158e97dd71a467cd2e0c893059b10f78e6b72ada1ed2c919";
+
+/// Serializes a `DateTime<Local>` in the `YYYYMMDDHHmmss` format M-Pesa
+/// expects for `Timestamp` fields.
+pub(crate) fn serialize_utc_to_string<S>(
+    date: &DateTime<Local>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&date.format("%Y%m%d%H%M%S").to_string())
+}
+
+impl Mpesa {
+    /// Creates a `MpesaExpressQueryBuilder` for checking the status of an
+    /// STK push transaction previously initiated via `Mpesa::express`.
+    pub fn express_query(&self) -> MpesaExpressQueryBuilder {
+        MpesaExpressQuery::builder(self)
+    }
+}
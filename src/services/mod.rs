@@ -0,0 +1,2 @@
+pub mod business_paybill;
+pub mod express;